@@ -0,0 +1,31 @@
+//! ## fs
+//!
+//! minimal filesystem entry type `ExplorerTree` nodes carry; the real SFTP/FTP/local clients
+//! that list directories and populate the tree live outside this snapshot
+
+/// ## FsEntry
+///
+/// A single file or directory entry, as listed by one of the filesystem clients
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FsEntry {
+    File { name: String },
+    Directory { name: String },
+}
+
+impl FsEntry {
+    /// ### is_dir
+    ///
+    /// Whether this entry is a directory
+    pub fn is_dir(&self) -> bool {
+        matches!(self, FsEntry::Directory { .. })
+    }
+
+    /// ### name
+    ///
+    /// This entry's display name
+    pub fn name(&self) -> String {
+        match self {
+            FsEntry::File { name } | FsEntry::Directory { name } => name.clone(),
+        }
+    }
+}
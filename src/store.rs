@@ -0,0 +1,155 @@
+//! ## Store
+//!
+//! persistent key/value store for transient UI and session state (last `Log` filter level,
+//! tree expansion set, scroll positions, a capped ring buffer of log lines, ...), loaded at
+//! startup and flushed to a single on-disk file on every change
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// -- well-known keys, so components don't have to agree on string literals by hand
+
+/// Minimum severity the `Log` tab should show on the next startup
+pub const LOG_FILTER_LEVEL_KEY: &str = "log.filter_level";
+/// Ring buffer of the most recent log lines, restored into the `Log` tab on startup
+pub const LOG_BUFFER_KEY: &str = "log.buffer";
+/// Set of paths currently expanded in the tree-view explorer
+pub const EXPLORER_TREE_EXPANDED_KEY: &str = "explorer_tree.expanded";
+/// Last known scroll position of a scrollable component, keyed by component name
+pub const SCROLL_POSITION_KEY_PREFIX: &str = "scroll.position.";
+
+/// ### scroll_position_key
+///
+/// Build the `Store` key under which `component`'s scroll/list position is persisted
+pub fn scroll_position_key(component: &str) -> String {
+    format!("{}{}", SCROLL_POSITION_KEY_PREFIX, component)
+}
+
+/// ## RingBuffer
+///
+/// A capped FIFO of lines: pushing past `cap` evicts the oldest entry first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RingBuffer {
+    cap: usize,
+    lines: VecDeque<String>,
+}
+
+impl RingBuffer {
+    /// ### new
+    ///
+    /// Instantiate an empty ring buffer holding at most `cap` lines (a `cap` of 0 still keeps
+    /// the latest line)
+    pub fn new(cap: usize) -> Self {
+        Self {
+            cap,
+            lines: VecDeque::with_capacity(cap),
+        }
+    }
+
+    /// ### push
+    ///
+    /// Append `line`, evicting the oldest entry if the buffer is already at capacity
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() >= self.cap.max(1) {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// ### iter
+    ///
+    /// Iterate the buffered lines, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &String> {
+        self.lines.iter()
+    }
+}
+
+/// ## Store
+///
+/// Central key/value store for state that should survive between sessions. Values are
+/// type-erased JSON internally, so callers get a typed `get`/`set` without the store having
+/// to know about every component's state type up front.
+pub struct Store {
+    path: PathBuf,
+    values: HashMap<String, serde_json::Value>,
+}
+
+impl Store {
+    /// ### load
+    ///
+    /// Load the store from `path`, starting empty if the file doesn't exist or is corrupt
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let values = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { path, values }
+    }
+
+    /// ### get
+    ///
+    /// Retrieve and deserialize the value at `key`, or `None` if absent/of the wrong shape
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.values
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// ### set
+    ///
+    /// Serialize `value` into `key` and flush the store to disk
+    pub fn set<T: Serialize>(&mut self, key: &str, value: T) {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.values.insert(key.to_string(), value);
+        }
+        // Best-effort: a failed flush shouldn't take down the UI, the in-memory value still
+        // gets used for the rest of this session
+        let _ = self.flush();
+    }
+
+    /// ### flush
+    ///
+    /// Persist the store to its backing file
+    pub fn flush(&self) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let serialized = serde_json::to_string_pretty(&self.values)
+            .unwrap_or_else(|_| "{}".to_string());
+        fs::write(&self.path, serialized)
+    }
+
+    /// ### path
+    ///
+    /// Path to the backing file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
@@ -0,0 +1,497 @@
+//! ## ExplorerTree
+//!
+//! collapsible tree-view file explorer component
+//!
+//! Built directly on `orange-trees`, which `tui-realm-treeview` itself wraps, rather than on
+//! `tui-realm-treeview`'s own `TreeView` widget: that widget's `Node` only carries an id and a
+//! display label, with no slot for the `FsEntry` payload (kind, size, permissions, ...) this
+//! component needs per node, and its bundled selection/open-close state has no lazy-load hook
+//! to tell the host "fetch this directory's children". Flattening/selection here mirrors the
+//! stateful-list pattern already used by `Log` instead.
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+use super::{Msg, UiMsg};
+
+use crate::fs::FsEntry;
+use crate::store::{scroll_position_key, Store, EXPLORER_TREE_EXPANDED_KEY};
+
+use orange_trees::{Node, Tree};
+use tuirealm::command::{Cmd, CmdResult, Direction, Position};
+use tuirealm::event::{Key, KeyEvent};
+use tuirealm::props::{Alignment, AttrValue, Attribute, Borders, Color, Style};
+use tuirealm::tui::widgets::{List as TuiList, ListItem, ListState};
+use tuirealm::{Component, Event, MockComponent, NoUserEvent, Props, State, StateValue};
+
+pub struct ExplorerTree {
+    props: Props,
+    states: OwnStates,
+}
+
+impl ExplorerTree {
+    pub fn new(tree: Tree<String, FsEntry>, fg: Color, bg: Color) -> Self {
+        let mut props = Props::default();
+        props.set(
+            Attribute::Borders,
+            AttrValue::Borders(Borders::default().color(fg)),
+        );
+        props.set(Attribute::Background, AttrValue::Color(bg));
+        let mut states = OwnStates::default();
+        states.load_tree(tree);
+        Self { props, states }
+    }
+
+    /// ### load_tree
+    ///
+    /// Replace the backing tree (e.g. once a directory's freshly listed children have been
+    /// grafted onto it) and rebuild the visible view, keeping the current expansion set
+    pub fn load_tree(&mut self, tree: Tree<String, FsEntry>) {
+        self.states.load_tree(tree);
+    }
+
+    /// ### restore_from_store
+    ///
+    /// Restore the persisted expansion set and scroll position from `store` and rebuild the
+    /// visible view accordingly. Collapsed-but-unseen directories are lazily loaded again as
+    /// the user reaches them, same as on a fresh expand.
+    pub fn restore_from_store(&mut self, store: &Store) {
+        if let Some(expanded) = store.get::<Vec<String>>(EXPLORER_TREE_EXPANDED_KEY) {
+            self.states.expanded = expanded.into_iter().collect();
+            self.states.rebuild_visible();
+        }
+        if let Some(list_index) = store.get::<usize>(&scroll_position_key(STORE_COMPONENT_NAME)) {
+            self.states.list_index = list_index.min(self.states.visible.len().saturating_sub(1));
+        }
+    }
+
+    /// ### persist_to_store
+    ///
+    /// Flush the current expansion set and scroll position to `store`
+    pub fn persist_to_store(&self, store: &mut Store) {
+        let expanded: Vec<String> = self.states.expanded.iter().cloned().collect();
+        store.set(EXPLORER_TREE_EXPANDED_KEY, expanded);
+        store.set(
+            &scroll_position_key(STORE_COMPONENT_NAME),
+            self.states.list_index,
+        );
+    }
+}
+
+/// Component name this `ExplorerTree` is persisted under in the `Store`'s scroll-position keys
+const STORE_COMPONENT_NAME: &str = "explorer_tree";
+
+impl MockComponent for ExplorerTree {
+    fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
+        let focus = self
+            .props
+            .get_or(Attribute::Focus, AttrValue::Flag(false))
+            .unwrap_flag();
+        let fg = self
+            .props
+            .get_or(Attribute::Foreground, AttrValue::Color(Color::Reset))
+            .unwrap_color();
+        let bg = self
+            .props
+            .get_or(Attribute::Background, AttrValue::Color(Color::Reset))
+            .unwrap_color();
+        let list_items: Vec<ListItem> = self
+            .states
+            .visible
+            .iter()
+            .map(|entry| {
+                let prefix = "  ".repeat(entry.depth.saturating_sub(1));
+                let branch = match entry.depth {
+                    0 => "",
+                    _ if entry.is_last => "└─ ",
+                    _ => "├─ ",
+                };
+                let marker = if entry.is_dir {
+                    if entry.expanded {
+                        "v "
+                    } else {
+                        "> "
+                    }
+                } else {
+                    "  "
+                };
+                ListItem::new(format!("{}{}{}{}", prefix, branch, marker, entry.label))
+            })
+            .collect();
+        let w = TuiList::new(list_items)
+            .block(tui_realm_stdlib::utils::get_block(
+                Borders::default().color(fg),
+                Some(("Explorer".to_string(), Alignment::Left)),
+                focus,
+                None,
+            ))
+            .highlight_symbol(">> ")
+            .style(Style::default().bg(bg))
+            .highlight_style(Style::default().add_modifier(tuirealm::tui::style::Modifier::BOLD));
+        let mut state: ListState = ListState::default();
+        state.select(Some(self.states.list_index));
+        frame.render_stateful_widget(w, area, &mut state);
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value);
+    }
+
+    fn state(&self) -> State {
+        match self.states.selected_path() {
+            Some(path) => State::One(StateValue::String(path)),
+            None => State::None,
+        }
+    }
+
+    fn perform(&mut self, cmd: Cmd) -> CmdResult {
+        match cmd {
+            Cmd::Move(Direction::Down) => {
+                let prev = self.states.list_index;
+                self.states.incr_list_index();
+                if prev != self.states.list_index {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
+            }
+            Cmd::Move(Direction::Up) => {
+                let prev = self.states.list_index;
+                self.states.decr_list_index();
+                if prev != self.states.list_index {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
+            }
+            Cmd::Scroll(Direction::Down) => {
+                let prev = self.states.list_index;
+                (0..8).for_each(|_| self.states.incr_list_index());
+                if prev != self.states.list_index {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
+            }
+            Cmd::Scroll(Direction::Up) => {
+                let prev = self.states.list_index;
+                (0..8).for_each(|_| self.states.decr_list_index());
+                if prev != self.states.list_index {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
+            }
+            Cmd::GoTo(Position::Begin) => {
+                self.states.list_index = 0;
+                CmdResult::Changed(self.state())
+            }
+            Cmd::GoTo(Position::End) => {
+                self.states.list_index_at_last();
+                CmdResult::Changed(self.state())
+            }
+            Cmd::Move(Direction::Right) => match self.states.expand_selected() {
+                ExpandOutcome::NoChange => CmdResult::None,
+                ExpandOutcome::Expanded | ExpandOutcome::NeedsLoad(_) => {
+                    CmdResult::Changed(self.state())
+                }
+            },
+            Cmd::Move(Direction::Left) => {
+                if self.states.collapse_selected() {
+                    CmdResult::Changed(self.state())
+                } else {
+                    CmdResult::None
+                }
+            }
+            _ => CmdResult::None,
+        }
+    }
+}
+
+impl Component<Msg, NoUserEvent> for ExplorerTree {
+    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        match ev {
+            Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
+                self.perform(Cmd::Move(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Down, ..
+            }) => {
+                self.perform(Cmd::Move(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageUp, ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Up));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::PageDown,
+                ..
+            }) => {
+                self.perform(Cmd::Scroll(Direction::Down));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::Home, .. }) => {
+                self.perform(Cmd::GoTo(Position::Begin));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent { code: Key::End, .. }) => {
+                self.perform(Cmd::GoTo(Position::End));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Right, ..
+            }) => match self.states.expand_selected() {
+                ExpandOutcome::NeedsLoad(path) => {
+                    Some(Msg::Ui(UiMsg::ExplorerTreeExpandRequested(path)))
+                }
+                ExpandOutcome::Expanded | ExpandOutcome::NoChange => Some(Msg::None),
+            },
+            Event::Keyboard(KeyEvent { code: Key::Left, .. }) => {
+                self.perform(Cmd::Move(Direction::Left));
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Enter, ..
+            }) => match self.states.toggle_selected() {
+                ToggleOutcome::NeedsLoad(path) => {
+                    Some(Msg::Ui(UiMsg::ExplorerTreeExpandRequested(path)))
+                }
+                ToggleOutcome::Expanded | ToggleOutcome::Collapsed => Some(Msg::None),
+                ToggleOutcome::NotADirectory => self
+                    .states
+                    .selected_path()
+                    .map(|path| Msg::Ui(UiMsg::ExplorerTreeSelected(path))),
+            },
+            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
+                Some(Msg::Ui(UiMsg::ExplorerTreeTabbed))
+            }
+            _ => None,
+        }
+    }
+}
+
+// -- states
+
+/// ## FlatNode
+///
+/// A single row of the flattened, currently-visible view of the tree
+#[derive(Clone)]
+struct FlatNode {
+    path: String,
+    label: String,
+    depth: usize,
+    is_dir: bool,
+    expanded: bool,
+    is_last: bool, // Whether this is the last child among its siblings (picks the box-drawing glyph)
+}
+
+/// ## ExpandOutcome
+///
+/// Result of attempting to expand the selected directory
+enum ExpandOutcome {
+    /// Selection isn't an expandable, collapsed directory; nothing happened
+    NoChange,
+    /// Expanded using children already present in the tree
+    Expanded,
+    /// Expanded, but its children were never fetched: the host must graft them onto the tree
+    /// (e.g. via a directory listing) and call `load_tree`/trigger a rebuild before the user
+    /// descends any further, or the subtree will just show empty
+    NeedsLoad(String),
+}
+
+/// ## ToggleOutcome
+///
+/// Result of toggling expand/collapse on the selected node
+enum ToggleOutcome {
+    /// Selection isn't a directory, or nothing is selected
+    NotADirectory,
+    /// Collapsed
+    Collapsed,
+    /// Expanded using children already present in the tree
+    Expanded,
+    /// Expanded, but its children must be fetched by the host (see `ExpandOutcome::NeedsLoad`)
+    NeedsLoad(String),
+}
+
+/// ## OwnStates
+///
+/// OwnStates contains states for this component
+#[derive(Default)]
+struct OwnStates {
+    tree: Option<Tree<String, FsEntry>>,
+    expanded: std::collections::HashSet<String>, // Paths of directories currently expanded
+    loaded: std::collections::HashSet<String>,   // Paths of directories whose children were fetched
+    visible: Vec<FlatNode>,                      // Flattened view of the currently visible nodes
+    list_index: usize,                           // Index of selected element in `visible`
+}
+
+impl OwnStates {
+    /// ### load_tree
+    ///
+    /// Replace the backing tree and rebuild the flattened visible view
+    pub fn load_tree(&mut self, tree: Tree<String, FsEntry>) {
+        self.tree = Some(tree);
+        self.rebuild_visible();
+    }
+
+    /// ### rebuild_visible
+    ///
+    /// Flatten the tree into `visible`, descending into expanded directories only
+    fn rebuild_visible(&mut self) {
+        self.visible.clear();
+        let root = match self.tree.as_ref().map(|t| t.root()) {
+            Some(root) => root.clone(),
+            None => return,
+        };
+        self.flatten(&root, 0, true);
+        if self.list_index >= self.visible.len() {
+            self.list_index_at_last();
+        }
+    }
+
+    /// ### flatten
+    ///
+    /// Recursively append `node` and, if it's an expanded directory, its children to `visible`.
+    /// `is_last` tells whether `node` is the last child among its siblings, so `view` can pick
+    /// the right box-drawing glyph.
+    fn flatten(&mut self, node: &Node<String, FsEntry>, depth: usize, is_last: bool) {
+        let is_dir = node.value().is_dir();
+        let expanded = self.expanded.contains(node.id());
+        if depth > 0 {
+            self.visible.push(FlatNode {
+                path: node.id().clone(),
+                label: node.value().name(),
+                depth,
+                is_dir,
+                expanded,
+                is_last,
+            });
+        }
+        if depth == 0 || (is_dir && expanded) {
+            let children: Vec<&Node<String, FsEntry>> = node.children().iter().collect();
+            let last_idx = children.len().saturating_sub(1);
+            for (idx, child) in children.into_iter().enumerate() {
+                self.flatten(child, depth + 1, idx == last_idx);
+            }
+        }
+    }
+
+    /// ### selected_path
+    ///
+    /// Return the path of the currently selected node, if any
+    pub fn selected_path(&self) -> Option<String> {
+        self.visible.get(self.list_index).map(|n| n.path.clone())
+    }
+
+    /// ### incr_list_index
+    pub fn incr_list_index(&mut self) {
+        if self.list_index + 1 < self.visible.len() {
+            self.list_index += 1;
+        }
+    }
+
+    /// ### decr_list_index
+    pub fn decr_list_index(&mut self) {
+        if self.list_index > 0 {
+            self.list_index -= 1;
+        }
+    }
+
+    /// ### list_index_at_last
+    pub fn list_index_at_last(&mut self) {
+        self.list_index = self.visible.len().saturating_sub(1);
+    }
+
+    /// ### expand_selected
+    ///
+    /// Expand the selected directory. If its children were never fetched, the expansion still
+    /// happens (so the directory no longer looks collapsed) but the caller gets back
+    /// `ExpandOutcome::NeedsLoad` and must graft the real children onto the tree and rebuild;
+    /// until then the subtree renders empty.
+    pub fn expand_selected(&mut self) -> ExpandOutcome {
+        let (path, is_dir) = match self.visible.get(self.list_index) {
+            Some(node) => (node.path.clone(), node.is_dir),
+            None => return ExpandOutcome::NoChange,
+        };
+        if !is_dir || self.expanded.contains(&path) {
+            return ExpandOutcome::NoChange;
+        }
+        let needs_load = !self.loaded.contains(&path);
+        if needs_load {
+            self.loaded.insert(path.clone());
+        }
+        self.expanded.insert(path.clone());
+        self.rebuild_visible();
+        if needs_load {
+            ExpandOutcome::NeedsLoad(path)
+        } else {
+            ExpandOutcome::Expanded
+        }
+    }
+
+    /// ### collapse_selected
+    ///
+    /// Collapse the selected directory. Returns whether the view changed.
+    pub fn collapse_selected(&mut self) -> bool {
+        let path = match self.visible.get(self.list_index) {
+            Some(node) if node.is_dir => node.path.clone(),
+            _ => return false,
+        };
+        if !self.expanded.remove(&path) {
+            return false;
+        }
+        self.rebuild_visible();
+        true
+    }
+
+    /// ### toggle_selected
+    ///
+    /// Toggle expand/collapse on the selected directory
+    pub fn toggle_selected(&mut self) -> ToggleOutcome {
+        let (is_dir, expanded) = match self.visible.get(self.list_index) {
+            Some(node) => (node.is_dir, node.expanded),
+            None => return ToggleOutcome::NotADirectory,
+        };
+        if !is_dir {
+            return ToggleOutcome::NotADirectory;
+        }
+        if expanded {
+            self.collapse_selected();
+            ToggleOutcome::Collapsed
+        } else {
+            match self.expand_selected() {
+                ExpandOutcome::NoChange => ToggleOutcome::NotADirectory,
+                ExpandOutcome::Expanded => ToggleOutcome::Expanded,
+                ExpandOutcome::NeedsLoad(path) => ToggleOutcome::NeedsLoad(path),
+            }
+        }
+    }
+}
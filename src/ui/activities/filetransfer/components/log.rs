@@ -27,12 +27,60 @@
  */
 use super::{Msg, UiMsg};
 
+use crate::store::{scroll_position_key, RingBuffer, Store, LOG_BUFFER_KEY, LOG_FILTER_LEVEL_KEY};
+
+use std::io::Write;
+use std::path::Path;
+
 use tuirealm::command::{Cmd, CmdResult, Direction, Position};
 use tuirealm::event::{Key, KeyEvent};
-use tuirealm::props::{Alignment, AttrValue, Attribute, Borders, Color, Style, Table};
-use tuirealm::tui::layout::Corner;
-use tuirealm::tui::widgets::{List as TuiList, ListItem, ListState};
+use tuirealm::props::{Alignment, AttrValue, Attribute, Borders, Color, Style, Table, TextSpan};
+use tuirealm::tui::text::{Line, Span};
+use tuirealm::tui::widgets::{List as TuiList, ListDirection, ListItem, ListState};
 use tuirealm::{Component, Event, MockComponent, NoUserEvent, Props, State, StateValue};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Minimum usable width (in columns) below which we render an empty line rather than wrap
+const MIN_RENDER_WIDTH: usize = 1;
+/// Highlight symbol rendered in front of the selected row (see `TuiList::highlight_symbol`)
+const HIGHLIGHT_SYMBOL: &str = ">> ";
+/// Capacity of the ring buffer persisted to the `Store`
+const LOG_RING_BUFFER_CAP: usize = 1000;
+/// Component name this `Log` is persisted under in the `Store`'s scroll-position keys
+const STORE_COMPONENT_NAME: &str = "log";
+
+/// ### wrap_row
+///
+/// Wrap `row` into lines at most `width` display columns wide, measuring width with
+/// `unicode-width` over grapheme clusters so CJK/emoji and combining marks aren't mangled.
+/// A grapheme is never split across lines: if adding it would overflow the line, it starts
+/// the next one instead. Zero-width graphemes (e.g. combining marks) always attach to the
+/// current line, even at the boundary.
+fn wrap_row(row: &[TextSpan], width: usize) -> Vec<Line<'static>> {
+    if width < MIN_RENDER_WIDTH {
+        return vec![Line::from("")];
+    }
+    let mut lines: Vec<Line> = Vec::new();
+    let mut current: Vec<Span> = Vec::new();
+    let mut current_width = 0usize;
+    for span in row.iter() {
+        let style = Style::default().fg(span.fg).bg(span.bg);
+        for grapheme in span.content.as_str().graphemes(true) {
+            let grapheme_width = grapheme.width();
+            if grapheme_width > 0 && current_width + grapheme_width > width {
+                lines.push(Line::from(std::mem::take(&mut current)));
+                current_width = 0;
+            }
+            current.push(Span::styled(grapheme.to_string(), style));
+            current_width += grapheme_width;
+        }
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(Line::from(current));
+    }
+    lines
+}
 
 pub struct Log {
     props: Props,
@@ -48,16 +96,150 @@ impl Log {
         );
         props.set(Attribute::Background, AttrValue::Color(bg));
         props.set(Attribute::Content, AttrValue::Table(lines));
-        Self {
-            props,
-            states: OwnStates::default(),
+        let mut states = OwnStates::default();
+        states.rebuild_visible(&props.get(Attribute::Content).unwrap().unwrap_table());
+        Self { props, states }
+    }
+
+    /// ### restore_from_store
+    ///
+    /// Restore the persisted filter level and scroll position from `store`, applied on top of
+    /// whatever `Content` was set at construction time. The backing buffer itself is supplied
+    /// by the caller (typically from `store.get(LOG_BUFFER_KEY)`) via `Log::new`/`attr`, since
+    /// the store only knows about plain lines, not this component's row/span representation.
+    pub fn restore_from_store(&mut self, store: &Store) {
+        if let Some(level) = store
+            .get::<String>(LOG_FILTER_LEVEL_KEY)
+            .and_then(|label| Severity::from_label(&label))
+        {
+            self.states.filter_level = level;
+        }
+        let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+        self.states.rebuild_visible(&table);
+        if let Some(list_index) = store.get::<usize>(&scroll_position_key(STORE_COMPONENT_NAME)) {
+            self.states.list_index = list_index.min(self.states.list_len.saturating_sub(1));
+        }
+    }
+
+    /// ### persist_to_store
+    ///
+    /// Flush the current filter level, scroll position and the full (unfiltered) buffer to
+    /// `store`
+    pub fn persist_to_store(&self, store: &mut Store) {
+        store.set(LOG_FILTER_LEVEL_KEY, self.states.filter_level.label());
+        store.set(
+            &scroll_position_key(STORE_COMPONENT_NAME),
+            self.states.get_list_index(),
+        );
+        let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+        let mut buffer = RingBuffer::new(LOG_RING_BUFFER_CAP);
+        // Row 0 is the newest entry; push oldest first so the ring buffer evicts the oldest
+        for row in table.iter().rev() {
+            buffer.push(row.iter().map(|span| span.content.as_str()).collect());
+        }
+        store.set(LOG_BUFFER_KEY, buffer);
+    }
+
+    /// ### dump
+    ///
+    /// Serialize the full (unfiltered) log buffer to `path` as plain text, one line per row,
+    /// so users can attach session logs to bug reports
+    pub fn dump(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+        let mut file = std::fs::File::create(path)?;
+        for row in table.iter() {
+            let text: String = row.iter().map(|span| span.content.as_str()).collect();
+            writeln!(file, "{}", text)?;
+        }
+        Ok(())
+    }
+}
+
+/// ## Severity
+///
+/// Severity recognized from the leading token of a log row
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    /// ### parse
+    ///
+    /// Parse the severity of a row from its leading whitespace-delimited token.
+    /// Rows without a recognized token are treated as `Info`, so they're filtered out only
+    /// by levels stricter than `Info`.
+    fn parse(row: &[TextSpan]) -> Self {
+        let text: String = row.iter().map(|span| span.content.as_str()).collect();
+        match text.split_whitespace().next() {
+            Some("DEBUG") => Severity::Debug,
+            Some("WARN") => Severity::Warn,
+            Some("ERROR") => Severity::Error,
+            _ => Severity::Info,
+        }
+    }
+
+    /// ### color
+    ///
+    /// Style associated to this severity level
+    fn color(&self) -> Color {
+        match self {
+            Severity::Debug => Color::Gray,
+            Severity::Info => Color::Reset,
+            Severity::Warn => Color::Yellow,
+            Severity::Error => Color::Red,
+        }
+    }
+
+    /// ### next
+    ///
+    /// Cycle to the next (stricter) filter level, wrapping back to `Debug`
+    fn next(&self) -> Self {
+        match self {
+            Severity::Debug => Severity::Info,
+            Severity::Info => Severity::Warn,
+            Severity::Warn => Severity::Error,
+            Severity::Error => Severity::Debug,
+        }
+    }
+
+    /// ### label
+    ///
+    /// Short label used in the block title and when persisting to the `Store`
+    fn label(&self) -> &'static str {
+        match self {
+            Severity::Debug => "DEBUG",
+            Severity::Info => "INFO",
+            Severity::Warn => "WARN",
+            Severity::Error => "ERROR",
+        }
+    }
+
+    /// ### from_label
+    ///
+    /// Parse a severity previously persisted via `label`
+    fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "DEBUG" => Some(Severity::Debug),
+            "INFO" => Some(Severity::Info),
+            "WARN" => Some(Severity::Warn),
+            "ERROR" => Some(Severity::Error),
+            _ => None,
         }
     }
 }
 
 impl MockComponent for Log {
     fn view(&mut self, frame: &mut tuirealm::Frame, area: tuirealm::tui::layout::Rect) {
-        let width: usize = area.width as usize - 4;
+        // `area.width` also accounts for borders (2) and inner padding (2); reserve the
+        // highlight symbol's display width too, so it never pushes wide glyphs off-screen.
+        // `saturating_sub` keeps this safe on panes narrower than the reserved columns.
+        let width: usize = (area.width as usize)
+            .saturating_sub(4)
+            .saturating_sub(HIGHLIGHT_SYMBOL.width());
         let focus = self
             .props
             .get_or(Attribute::Focus, AttrValue::Flag(false))
@@ -70,24 +252,49 @@ impl MockComponent for Log {
             .props
             .get_or(Attribute::Background, AttrValue::Color(Color::Reset))
             .unwrap_color();
-        // Make list
+        let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+        // Make list from the filtered (visible) projection, highlighting rows that match the
+        // current search and coloring each row by its severity
         let list_items: Vec<ListItem> = self
-            .props
-            .get(Attribute::Content)
-            .unwrap()
-            .unwrap_table()
+            .states
+            .visible
             .iter()
-            .map(|row| ListItem::new(tui_realm_stdlib::utils::wrap_spans(row, width, &self.props)))
+            .enumerate()
+            .map(|(pos, &row_idx)| {
+                let row = &table[row_idx];
+                let text = wrap_row(row, width);
+                let style = Style::default().fg(Severity::parse(row).color());
+                if self.states.matches.contains(&pos) {
+                    ListItem::new(text).style(style.add_modifier(
+                        tuirealm::tui::style::Modifier::BOLD | tuirealm::tui::style::Modifier::REVERSED,
+                    ))
+                } else {
+                    ListItem::new(text).style(style)
+                }
+            })
             .collect();
+        let title = match self.states.search_query.as_ref() {
+            Some(query) => format!(
+                "Log [>={}] (/{} {}/{})",
+                self.states.filter_level.label(),
+                query,
+                self.states
+                    .current_match
+                    .map(|i| i + 1)
+                    .unwrap_or(0),
+                self.states.matches.len()
+            ),
+            None => format!("Log [>={}]", self.states.filter_level.label()),
+        };
         let w = TuiList::new(list_items)
             .block(tui_realm_stdlib::utils::get_block(
                 Borders::default().color(fg),
-                Some(("Log".to_string(), Alignment::Left)),
+                Some((title, Alignment::Left)),
                 focus,
                 None,
             ))
-            .start_corner(Corner::BottomLeft)
-            .highlight_symbol(">> ")
+            .direction(ListDirection::BottomToTop)
+            .highlight_symbol(HIGHLIGHT_SYMBOL)
             .style(Style::default().bg(bg))
             .highlight_style(Style::default());
         let mut state: ListState = ListState::default();
@@ -102,13 +309,13 @@ impl MockComponent for Log {
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
         self.props.set(attr, value);
         if matches!(attr, Attribute::Content) {
-            self.states.set_list_len(
-                match self.props.get(Attribute::Content).map(|x| x.unwrap_table()) {
-                    Some(spans) => spans.len(),
-                    _ => 0,
-                },
-            );
+            let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+            self.states.rebuild_visible(&table);
             self.states.reset_list_index();
+            // New lines can shift match positions (or the filter can drop/admit rows), so a
+            // search in progress must be recomputed against the freshly rebuilt `visible`
+            // projection rather than left pointing at stale indices
+            self.states.recompute_matches(&table);
         }
     }
 
@@ -179,7 +386,72 @@ impl MockComponent for Log {
 
 impl Component<Msg, NoUserEvent> for Log {
     fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+        // -- search mode intercepts most keys while active
+        if self.states.is_searching() {
+            return match ev {
+                Event::Keyboard(KeyEvent {
+                    code: Key::Char(c), ..
+                }) => {
+                    self.states.push_search_char(c);
+                    let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+                    self.states.recompute_matches(&table);
+                    Some(Msg::Ui(UiMsg::LogSearchUpdated))
+                }
+                Event::Keyboard(KeyEvent {
+                    code: Key::Backspace,
+                    ..
+                }) => {
+                    self.states.pop_search_char();
+                    let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+                    self.states.recompute_matches(&table);
+                    Some(Msg::Ui(UiMsg::LogSearchUpdated))
+                }
+                Event::Keyboard(KeyEvent {
+                    code: Key::Esc, ..
+                })
+                | Event::Keyboard(KeyEvent {
+                    code: Key::Enter, ..
+                }) => {
+                    self.states.exit_search();
+                    Some(Msg::Ui(UiMsg::LogSearchEnded))
+                }
+                _ => Some(Msg::None),
+            };
+        }
         match ev {
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('/'),
+                ..
+            }) => {
+                self.states.enter_search();
+                Some(Msg::Ui(UiMsg::LogSearchStarted))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('n'),
+                ..
+            }) => {
+                self.states.next_match();
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('N'),
+                ..
+            }) => {
+                self.states.prev_match();
+                Some(Msg::None)
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('f'),
+                ..
+            }) => {
+                let table = self.props.get(Attribute::Content).unwrap().unwrap_table();
+                self.states.cycle_filter_level(&table);
+                Some(Msg::Ui(UiMsg::LogFilterChanged))
+            }
+            Event::Keyboard(KeyEvent {
+                code: Key::Char('d'),
+                ..
+            }) => Some(Msg::Ui(UiMsg::LogDumpRequested)),
             Event::Keyboard(KeyEvent { code: Key::Up, .. }) => {
                 self.perform(Cmd::Move(Direction::Down));
                 Some(Msg::None)
@@ -227,9 +499,13 @@ impl Component<Msg, NoUserEvent> for Log {
 /// OwnStates contains states for this component
 #[derive(Clone)]
 struct OwnStates {
-    list_index: usize, // Index of selected element in list
-    list_len: usize,   // Length of file list
-    focus: bool,       // Has focus?
+    list_index: usize, // Index of selected element in the *filtered* view
+    list_len: usize,   // Length of the filtered view
+    search_query: Option<String>, // Current search query, `Some` while in search mode
+    matches: Vec<usize>,          // Positions (in the filtered view) matching `search_query`
+    current_match: Option<usize>, // Index into `matches` of the active match
+    filter_level: Severity, // Minimum severity shown in the filtered view
+    visible: Vec<usize>,   // Indexes into the full (unfiltered) table that pass `filter_level`
 }
 
 impl Default for OwnStates {
@@ -237,19 +513,16 @@ impl Default for OwnStates {
         OwnStates {
             list_index: 0,
             list_len: 0,
-            focus: false,
+            search_query: None,
+            matches: Vec::new(),
+            current_match: None,
+            filter_level: Severity::Debug,
+            visible: Vec::new(),
         }
     }
 }
 
 impl OwnStates {
-    /// ### set_list_len
-    ///
-    /// Set list length
-    pub fn set_list_len(&mut self, len: usize) {
-        self.list_len = len;
-    }
-
     /// ### get_list_index
     ///
     /// Return current value for list index
@@ -293,4 +566,144 @@ impl OwnStates {
     pub fn reset_list_index(&mut self) {
         self.list_index = 0; // Last element is always 0
     }
+
+    // -- search
+
+    /// ### is_searching
+    ///
+    /// Returns whether the component is currently capturing a search query
+    pub fn is_searching(&self) -> bool {
+        self.search_query.is_some()
+    }
+
+    /// ### enter_search
+    ///
+    /// Enter search mode, starting from an empty query
+    pub fn enter_search(&mut self) {
+        self.search_query = Some(String::new());
+        self.matches.clear();
+        self.current_match = None;
+    }
+
+    /// ### exit_search
+    ///
+    /// Leave search mode, keeping the last match highlighted
+    pub fn exit_search(&mut self) {
+        self.search_query = None;
+    }
+
+    /// ### push_search_char
+    ///
+    /// Append a char to the current search query
+    pub fn push_search_char(&mut self, c: char) {
+        if let Some(query) = self.search_query.as_mut() {
+            query.push(c);
+        }
+    }
+
+    /// ### pop_search_char
+    ///
+    /// Remove the last char from the current search query
+    pub fn pop_search_char(&mut self) {
+        if let Some(query) = self.search_query.as_mut() {
+            query.pop();
+        }
+    }
+
+    /// ### recompute_matches
+    ///
+    /// Rescan the filtered view of `table` for rows matching the current search query
+    /// (case-insensitive substring over the concatenated span text) and realign
+    /// `current_match`/`list_index`. Matches are positions in the filtered view, not the
+    /// full table, so they stay consistent with `list_index` and `Cmd` handling.
+    pub fn recompute_matches(&mut self, table: &Table) {
+        self.matches.clear();
+        let query = match self.search_query.as_ref() {
+            Some(query) if !query.is_empty() => query.to_lowercase(),
+            _ => {
+                self.current_match = None;
+                return;
+            }
+        };
+        for (pos, &row_idx) in self.visible.iter().enumerate() {
+            let text: String = table[row_idx]
+                .iter()
+                .map(|span| span.content.as_str())
+                .collect();
+            if text.to_lowercase().contains(&query) {
+                self.matches.push(pos);
+            }
+        }
+        self.current_match = if self.matches.is_empty() { None } else { Some(0) };
+        self.sync_list_index_to_current_match();
+    }
+
+    // -- filtering
+
+    /// ### rebuild_visible
+    ///
+    /// Recompute the visible projection of `table` for the current `filter_level`, keeping
+    /// the full table untouched so filtering stays non-destructive
+    pub fn rebuild_visible(&mut self, table: &Table) {
+        self.visible = table
+            .iter()
+            .enumerate()
+            .filter(|(_, row)| Severity::parse(row) >= self.filter_level)
+            .map(|(idx, _)| idx)
+            .collect();
+        self.list_len = self.visible.len();
+        if self.list_index >= self.list_len {
+            self.list_index_at_last();
+        }
+    }
+
+    /// ### cycle_filter_level
+    ///
+    /// Cycle the minimum visible severity and rebuild the visible projection accordingly
+    pub fn cycle_filter_level(&mut self, table: &Table) {
+        self.filter_level = self.filter_level.next();
+        self.rebuild_visible(table);
+        self.reset_list_index();
+        self.recompute_matches(table);
+    }
+
+    /// ### next_match
+    ///
+    /// Move `current_match` forward, wrapping around, updating `list_index`
+    pub fn next_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let next = match self.current_match {
+            Some(i) => (i + 1) % self.matches.len(),
+            None => 0,
+        };
+        self.current_match = Some(next);
+        self.sync_list_index_to_current_match();
+    }
+
+    /// ### prev_match
+    ///
+    /// Move `current_match` backward, wrapping around, updating `list_index`
+    pub fn prev_match(&mut self) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let prev = match self.current_match {
+            Some(0) => self.matches.len() - 1,
+            Some(i) => i - 1,
+            None => 0,
+        };
+        self.current_match = Some(prev);
+        self.sync_list_index_to_current_match();
+    }
+
+    /// ### sync_list_index_to_current_match
+    ///
+    /// Align `list_index` with the row of the active match, if any
+    fn sync_list_index_to_current_match(&mut self) {
+        if let Some(row) = self.current_match.and_then(|i| self.matches.get(i)) {
+            self.list_index = *row;
+        }
+    }
 }
@@ -0,0 +1,8 @@
+//! ## components
+//!
+//! tuirealm 1.x components used by `FileTransferActivity`
+
+pub mod explorer_tree;
+pub mod log;
+
+pub use super::{Msg, UiMsg};
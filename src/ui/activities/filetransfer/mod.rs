@@ -0,0 +1,209 @@
+//! ## FileTransferActivity
+//!
+//! activity driving the file transfer UI (local/remote explorers and the transfer log); owns
+//! the components and the `Store` they persist state through, and decides what to do with the
+//! `UiMsg`s they can't act on by themselves
+
+/**
+ * MIT License
+ *
+ * termscp - Copyright (c) 2021 Christian Visintin
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy
+ * of this software and associated documentation files (the "Software"), to deal
+ * in the Software without restriction, including without limitation the rights
+ * to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+ * copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all
+ * copies or substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+ * IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+ * FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+ * AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+ * LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+ * OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+ * SOFTWARE.
+ */
+pub mod components;
+
+use components::explorer_tree::ExplorerTree;
+use components::log::Log;
+use crate::store::Store;
+use std::path::PathBuf;
+
+/// ## Msg
+///
+/// Message returned by a component's `on()` to tell the activity what happened
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Msg {
+    /// Nothing the activity needs to act on
+    None,
+    /// A UI-level event only the activity can act on, since it needs state or I/O the
+    /// component itself doesn't own (the `Store`, focus across components, ...)
+    Ui(UiMsg),
+}
+
+/// ## UiMsg
+///
+/// Events emitted by `FileTransferActivity`'s components that the components themselves can't
+/// act on
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UiMsg {
+    /// `Tab` was pressed on `Log`: move focus to the next component
+    LogTabbed,
+    /// `Log` entered incremental-search mode
+    LogSearchStarted,
+    /// The search query or its matches changed
+    LogSearchUpdated,
+    /// Incremental search was dismissed (`Esc`/`Enter`)
+    LogSearchEnded,
+    /// The severity filter was cycled
+    LogFilterChanged,
+    /// `Tab` was pressed on `ExplorerTree`: move focus to the next component
+    ExplorerTreeTabbed,
+    /// A file (not a directory) was activated in the tree
+    ExplorerTreeSelected(String),
+    /// A collapsed directory with unfetched children was expanded; the host's filesystem
+    /// client needs to list `path` and graft the results onto the tree before the next redraw
+    ExplorerTreeExpandRequested(String),
+    /// `d` was pressed on `Log`: dump the full (unfiltered) buffer to disk
+    LogDumpRequested,
+}
+
+/// ## Focus
+///
+/// Which of this activity's components currently has focus
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Focus {
+    ExplorerTree,
+    Log,
+}
+
+/// ## FileTransferActivity
+///
+/// Owns the `ExplorerTree` and `Log` tabs and the `Store` they persist their filter/scroll/
+/// expansion state and the log buffer through. `ExplorerTree` replaces the previous flat file list.
+pub struct FileTransferActivity {
+    explorer_tree: ExplorerTree,
+    log: Log,
+    store: Store,
+    focus: Focus,
+    log_searching: bool,
+    selected_file: Option<String>,
+    /// Directory an `ExplorerTreeExpandRequested` asked to be listed, waiting on the host's
+    /// filesystem client (not part of this module) to fetch its children and graft them onto
+    /// the tree via `ExplorerTree::load_tree`
+    pending_expand: Option<String>,
+}
+
+impl FileTransferActivity {
+    /// ### new
+    ///
+    /// Instantiate the activity around already-mounted `ExplorerTree`/`Log` components, loading
+    /// the `Store` at `store_path` and restoring both components' persisted state from it
+    pub fn new(
+        mut explorer_tree: ExplorerTree,
+        mut log: Log,
+        store_path: impl Into<PathBuf>,
+    ) -> Self {
+        let store = Store::load(store_path);
+        explorer_tree.restore_from_store(&store);
+        log.restore_from_store(&store);
+        Self {
+            explorer_tree,
+            log,
+            store,
+            focus: Focus::ExplorerTree,
+            log_searching: false,
+            selected_file: None,
+            pending_expand: None,
+        }
+    }
+
+    /// ### persist
+    ///
+    /// Flush both components' scroll/filter/expansion state to the `Store`; call this before
+    /// the activity is torn down
+    pub fn persist(&mut self) {
+        self.explorer_tree.persist_to_store(&mut self.store);
+        self.log.persist_to_store(&mut self.store);
+    }
+
+    /// ### update
+    ///
+    /// Act on a `Msg` returned by one of this activity's components
+    pub fn update(&mut self, msg: Msg) {
+        if let Msg::Ui(msg) = msg {
+            self.update_ui(msg);
+        }
+    }
+
+    /// ### update_ui
+    ///
+    /// Act on a `UiMsg` a component emitted because it needed state or I/O it doesn't own
+    fn update_ui(&mut self, msg: UiMsg) {
+        match msg {
+            UiMsg::LogTabbed | UiMsg::ExplorerTreeTabbed => self.cycle_focus(),
+            UiMsg::LogSearchStarted => self.log_searching = true,
+            // The query and its matches already live on `Log` itself; the activity only
+            // needs to know search is active, which it already does
+            UiMsg::LogSearchUpdated => {}
+            UiMsg::LogSearchEnded => self.log_searching = false,
+            // Persist the new filter level immediately rather than only on exit, so it
+            // survives a crash or a kill -9 between now and the next graceful shutdown
+            UiMsg::LogFilterChanged => self.log.persist_to_store(&mut self.store),
+            UiMsg::ExplorerTreeSelected(path) => self.selected_file = Some(path),
+            // Fetching `path`'s children is the host's filesystem client's job (SFTP/FTP/
+            // local), which lives outside this activity; record the request so it isn't lost,
+            // ready for that client to drain, fetch, and graft via `ExplorerTree::load_tree`
+            UiMsg::ExplorerTreeExpandRequested(path) => self.pending_expand = Some(path),
+            UiMsg::LogDumpRequested => {
+                let path = self.store.path().with_file_name("termscp.log.txt");
+                if let Err(err) = self.log.dump(&path) {
+                    eprintln!("failed to dump log to {}: {}", path.display(), err);
+                }
+            }
+        }
+    }
+
+    /// ### cycle_focus
+    ///
+    /// Move focus to the other component
+    fn cycle_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::ExplorerTree => Focus::Log,
+            Focus::Log => Focus::ExplorerTree,
+        };
+    }
+
+    /// ### selected_file
+    ///
+    /// Path of the file last activated in `ExplorerTree`, if any
+    pub fn selected_file(&self) -> Option<&str> {
+        self.selected_file.as_deref()
+    }
+
+    /// ### take_pending_expand
+    ///
+    /// Take the directory path awaiting a listing from the host's filesystem client, if any
+    pub fn take_pending_expand(&mut self) -> Option<String> {
+        self.pending_expand.take()
+    }
+
+    /// ### is_log_searching
+    ///
+    /// Whether `Log`'s incremental search is currently active, e.g. for a status bar hint
+    pub fn is_log_searching(&self) -> bool {
+        self.log_searching
+    }
+
+    /// ### store
+    ///
+    /// Borrow the `Store` backing this activity's components
+    pub fn store(&mut self) -> &mut Store {
+        &mut self.store
+    }
+}
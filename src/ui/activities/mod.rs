@@ -0,0 +1,5 @@
+//! ## activities
+//!
+//! the application's full-screen activities
+
+pub mod filetransfer;
@@ -0,0 +1,7 @@
+//! ## components
+//!
+//! layout components built on the legacy `Canvas`/`Component` architecture in `layout::core`
+
+pub mod title;
+
+pub use super::core::{Canvas, Component, InputEvent, Msg, Payload, Props, PropsBuilder};
@@ -26,17 +26,42 @@
 // locals
 use super::{Canvas, Component, InputEvent, Msg, Payload, Props, PropsBuilder};
 // ext
+use std::cell::Cell;
 use tui::{layout::Rect, style::Style, widgets::Paragraph};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// ### TitleMode
+///
+/// Whether an overflowing title is truncated (`Static`) or scrolled as a marquee (`Scroll`),
+/// with `gap` columns of blank space separating the end of the text from its next loop
+#[derive(Clone, Copy, PartialEq)]
+pub enum TitleMode {
+    Static,
+    Scroll { gap: usize },
+}
+
+impl Default for TitleMode {
+    fn default() -> Self {
+        TitleMode::Scroll { gap: 4 }
+    }
+}
 
 // -- state
 
 struct OwnStates {
     focus: bool,
+    /// Current scroll offset, in graphemes, into the title + gap loop; advanced on each render
+    /// while a `Scroll`-mode title overflows its area
+    offset: Cell<usize>,
 }
 
 impl Default for OwnStates {
     fn default() -> Self {
-        OwnStates { focus: false }
+        OwnStates {
+            focus: false,
+            offset: Cell::new(0),
+        }
     }
 }
 
@@ -45,17 +70,59 @@ impl Default for OwnStates {
 pub struct Title {
     props: Props,
     states: OwnStates,
+    mode: TitleMode,
 }
 
 impl Title {
     /// ### new
     ///
-    /// Instantiate a new Title component
+    /// Instantiate a new Title component with the default `TitleMode` (scrolling). Alignment
+    /// is read from `props` like any other attribute
     pub fn new(props: Props) -> Self {
         Title {
             props,
             states: OwnStates::default(),
+            mode: TitleMode::default(),
+        }
+    }
+
+    /// ### with_mode
+    ///
+    /// Set whether an overflowing title should scroll as a marquee or stay static (truncated).
+    /// `TitleMode` has no equivalent in the generic `Props` shape shared by every layout
+    /// component, so unlike alignment it's configured here rather than through `PropsBuilder`
+    pub fn with_mode(mut self, mode: TitleMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// ### marquee_window
+    ///
+    /// Slice `loop_graphemes` (the title followed by `gap` blank columns) into the `width`
+    /// columns visible starting at `start`, wrapping around the loop. Slicing happens on
+    /// grapheme boundaries so wide characters are never split mid-cell.
+    fn marquee_window(loop_graphemes: &[&str], start: usize, width: usize) -> String {
+        if loop_graphemes.is_empty() {
+            return String::new();
+        }
+        let mut window = String::new();
+        let mut used = 0;
+        let mut i = start % loop_graphemes.len();
+        while used < width {
+            let grapheme = loop_graphemes[i];
+            let grapheme_width = grapheme.width();
+            if grapheme_width > 0 && used + grapheme_width > width {
+                break;
+            }
+            window.push_str(grapheme);
+            used += grapheme_width;
+            i = (i + 1) % loop_graphemes.len();
+            // Entire loop is narrower than `width`: stop instead of spinning forever
+            if i == start % loop_graphemes.len() {
+                break;
+            }
         }
+        window
     }
 }
 
@@ -64,7 +131,6 @@ impl Component for Title {
     ///
     /// Based on the current properties and states, renders a widget using the provided render engine in the provided Area
     /// If focused, cursor is also set (if supported by widget)
-    #[cfg(not(tarpaulin_include))]
     fn render(&self, render: &mut Canvas, area: Rect) {
         // Make a Span
         if self.props.visible {
@@ -72,13 +138,34 @@ impl Component for Title {
                 None => String::new(),
                 Some(t) => t.clone(),
             };
+            let width = area.width as usize;
+            let title_width = title.width();
+            let gap = match self.mode {
+                TitleMode::Scroll { gap } => gap,
+                TitleMode::Static => 0,
+            };
+            let visible = if self.mode == TitleMode::Static || title_width <= width || width == 0 {
+                title
+            } else {
+                // Loop the title with `gap` blank columns before it repeats, and advance the
+                // offset by one grapheme so the marquee scrolls on every redraw
+                let looped = format!("{}{}", title, " ".repeat(gap));
+                let graphemes: Vec<&str> = looped.graphemes(true).collect();
+                let offset = self.states.offset.get();
+                self.states
+                    .offset
+                    .set((offset + 1) % graphemes.len().max(1));
+                Self::marquee_window(&graphemes, offset, width)
+            };
             render.render_widget(
-                Paragraph::new(title).style(
-                    Style::default()
-                        .fg(self.props.foreground)
-                        .bg(self.props.background)
-                        .add_modifier(self.props.get_modifiers()),
-                ),
+                Paragraph::new(visible)
+                    .alignment(self.props.alignment)
+                    .style(
+                        Style::default()
+                            .fg(self.props.foreground)
+                            .bg(self.props.background)
+                            .add_modifier(self.props.get_modifiers()),
+                    ),
                 area,
             );
         }
@@ -112,11 +199,8 @@ impl Component for Title {
     /// Returns always None, since cannot have any focus
     fn on(&mut self, ev: InputEvent) -> Msg {
         // Return key
-        if let InputEvent::Key(key) = ev {
-            Msg::OnKey(key)
-        } else {
-            Msg::None
-        }
+        let InputEvent::Key(key) = ev;
+        Msg::OnKey(key)
     }
 
     /// ### get_value
@@ -160,11 +244,11 @@ mod tests {
                 .build(),
         );
         // Focus
-        assert_eq!(component.states.focus, false);
+        assert!(!component.states.focus);
         component.active();
-        assert_eq!(component.states.focus, true);
+        assert!(component.states.focus);
         component.blur();
-        assert_eq!(component.states.focus, false);
+        assert!(!component.states.focus);
         // Get value
         assert_eq!(component.get_value(), Payload::None);
         // Event
@@ -173,4 +257,33 @@ mod tests {
             Msg::OnKey(KeyEvent::from(KeyCode::Delete))
         );
     }
+
+    #[test]
+    fn test_ui_layout_components_title_marquee_window_fits_without_wrap() {
+        let looped: Vec<&str> = vec!["a", "b", "c", " ", " "];
+        assert_eq!(Title::marquee_window(&looped, 0, 10), "abc  ");
+    }
+
+    #[test]
+    fn test_ui_layout_components_title_marquee_window_wraps_around_loop() {
+        let looped: Vec<&str> = vec!["a", "b", "c", " "];
+        // Starting mid-loop, the window should wrap back to the start
+        assert_eq!(Title::marquee_window(&looped, 2, 4), "c ab");
+    }
+
+    #[test]
+    fn test_ui_layout_components_title_marquee_window_empty_loop() {
+        let looped: Vec<&str> = Vec::new();
+        assert_eq!(Title::marquee_window(&looped, 0, 5), "");
+    }
+
+    #[test]
+    fn test_ui_layout_components_title_marquee_window_never_splits_wide_grapheme() {
+        // "好" is 2 columns wide: at width 2 it fills the window exactly; starting at "a" with
+        // width 3, "a " uses 2 columns and "好" would need 2 more, so the window stops at "a "
+        // rather than split it
+        let looped: Vec<&str> = vec!["好", "a", " "];
+        assert_eq!(Title::marquee_window(&looped, 0, 2), "好");
+        assert_eq!(Title::marquee_window(&looped, 1, 3), "a ");
+    }
 }
@@ -0,0 +1,155 @@
+//! ## core
+//!
+//! the legacy `Canvas`/`Component` component architecture `title` is still built on; layout
+//! components are migrating off this onto `tuirealm` 1.x (see
+//! `ui::activities::filetransfer::components`) one at a time, so both live in the crate for now
+
+use crossterm::event::KeyEvent;
+use tui::backend::CrosstermBackend;
+use tui::layout::{Alignment, Rect};
+use tui::style::{Color, Modifier};
+use std::io::Stdout;
+
+/// Render target a `Component` draws itself onto
+pub type Canvas<'a> = tui::Frame<'a, CrosstermBackend<Stdout>>;
+
+/// ## TextParts
+///
+/// The pieces of text a component can be configured with
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextParts {
+    pub title: Option<String>,
+    pub body: Option<Vec<String>>,
+}
+
+impl TextParts {
+    /// ### new
+    ///
+    /// Instantiate a new `TextParts`
+    pub fn new(title: Option<String>, body: Option<Vec<String>>) -> Self {
+        Self { title, body }
+    }
+}
+
+/// ## Props
+///
+/// Generic properties shared by every legacy-architecture component
+#[derive(Debug, Clone)]
+pub struct Props {
+    pub visible: bool,
+    pub texts: TextParts,
+    pub foreground: Color,
+    pub background: Color,
+    pub alignment: Alignment,
+    modifiers: Modifier,
+}
+
+impl Default for Props {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            texts: TextParts::default(),
+            foreground: Color::Reset,
+            background: Color::Reset,
+            alignment: Alignment::Left,
+            modifiers: Modifier::empty(),
+        }
+    }
+}
+
+impl Props {
+    /// ### get_modifiers
+    ///
+    /// Style modifiers (bold, italic, ...) this component should render with
+    pub fn get_modifiers(&self) -> Modifier {
+        self.modifiers
+    }
+}
+
+/// ## PropsBuilder
+///
+/// Builds a `Props`, starting from either defaults or an existing component's properties
+#[derive(Debug, Clone, Default)]
+pub struct PropsBuilder {
+    props: Option<Props>,
+}
+
+impl PropsBuilder {
+    /// ### with_texts
+    ///
+    /// Set the component's `TextParts`
+    pub fn with_texts(mut self, texts: TextParts) -> Self {
+        self.props.get_or_insert_with(Props::default).texts = texts;
+        self
+    }
+
+    /// ### with_alignment
+    ///
+    /// Set the alignment to render the component's content with
+    pub fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.props.get_or_insert_with(Props::default).alignment = alignment;
+        self
+    }
+
+    /// ### build
+    ///
+    /// Consume the builder, producing the `Props` it describes
+    pub fn build(self) -> Props {
+        self.props.unwrap_or_default()
+    }
+}
+
+impl From<Props> for PropsBuilder {
+    fn from(props: Props) -> Self {
+        Self { props: Some(props) }
+    }
+}
+
+/// ## InputEvent
+///
+/// A raw input event dispatched to a component's `on()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputEvent {
+    Key(KeyEvent),
+}
+
+/// ## Msg
+///
+/// Message a legacy-architecture component's `on()`/`update()` returns to the view
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Msg {
+    /// An unhandled key, bubbled up for the view to route elsewhere
+    OnKey(KeyEvent),
+    /// Nothing the view needs to act on
+    None,
+}
+
+/// ## Payload
+///
+/// A component's current value, as returned by `get_value()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Payload {
+    /// The component holds no value (e.g. it's a readonly display like `Title`)
+    None,
+}
+
+/// ## Component
+///
+/// A widget in the legacy `Canvas`-based rendering architecture
+pub trait Component {
+    /// Render the component onto `area` of `render`
+    fn render(&self, render: &mut Canvas, area: Rect);
+    /// Replace this component's properties, returning a `Msg` for the view
+    fn update(&mut self, props: Props) -> Msg;
+    /// A builder seeded with this component's current properties, ready to be edited and
+    /// passed back to `update()`
+    fn get_props(&self) -> PropsBuilder;
+    /// Handle an input event, returning a `Msg` for the view
+    fn on(&mut self, ev: InputEvent) -> Msg;
+    /// This component's current value
+    fn get_value(&self) -> Payload;
+    /// Lose focus
+    fn blur(&mut self);
+    /// Gain focus
+    fn active(&mut self);
+}
@@ -0,0 +1,8 @@
+//! ## layout
+//!
+//! reusable layout components shared across activities, and the `props` types they're
+//! configured with
+
+pub mod components;
+pub mod core;
+pub mod props;
@@ -0,0 +1,6 @@
+//! ## props
+//!
+//! re-exports the prop types the legacy layout components in this module are built on, under
+//! a crate-local path so callers don't need to reach into `layout::core` directly
+
+pub use super::core::TextParts;
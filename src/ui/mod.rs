@@ -0,0 +1,6 @@
+//! ## ui
+//!
+//! activities (full-screen application states) and the layout components shared across them
+
+pub mod activities;
+pub mod layout;